@@ -0,0 +1,366 @@
+use std::{
+    fs::File,
+    io::Write,
+    path::PathBuf,
+    process::exit,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use base64::Engine;
+use clap::Args as ClapArgs;
+use hmac::{Hmac, Mac};
+use jwt::{AlgorithmType, FromBase64, VerifyingAlgorithm};
+use memmap2::Mmap;
+use rayon::{prelude::*, ThreadPoolBuilder};
+use sha2::{Sha256, Sha384, Sha512};
+
+use crate::rules;
+
+#[derive(ClapArgs, Debug)]
+pub struct Args {
+    /// JWT token to crack
+    jwt: String,
+
+    /// Path to the wordlist file containing potential secrets. Not needed
+    /// for RS256/384/512 tokens when --public-key is used, since that mode
+    /// never scans a wordlist; HS256/384/512 tokens always need it.
+    wordlist: Option<PathBuf>,
+
+    /// Number of threads to use (0 means use default thread count)
+    #[arg(short, long, default_value = "0")]
+    threads: usize,
+
+    /// Path to an RSA/EC public key PEM file. When the JWT header claims
+    /// RS256/384/512, this switches into algorithm-confusion mode: the
+    /// public key bytes are used as an HMAC secret instead of the wordlist.
+    #[arg(long)]
+    public_key: Option<PathBuf>,
+
+    /// Hex-encoded salt for PBKDF2. When set (together with the other
+    /// --pbkdf2-* flags), each wordlist entry is run through
+    /// PBKDF2-HMAC-SHA256 before being tried as the HMAC secret, instead
+    /// of being used literally.
+    #[arg(long)]
+    pbkdf2_salt: Option<String>,
+
+    /// Number of PBKDF2 iterations.
+    #[arg(long, default_value = "1", requires = "pbkdf2_salt")]
+    pbkdf2_iterations: u32,
+
+    /// Length in bytes of the derived PBKDF2 key.
+    #[arg(long, default_value = "32", requires = "pbkdf2_salt")]
+    pbkdf2_dklen: usize,
+
+    /// Path to a hashcat-style rules file. Each wordlist entry is expanded
+    /// into its rule-mangled variants (capitalize/upper/lower, append,
+    /// prepend, reverse, duplicate, digit ranges) before being tried as
+    /// the HMAC secret.
+    #[arg(long)]
+    rules: Option<PathBuf>,
+}
+
+pub fn run(args: Args) {
+    println!("warming up...");
+
+    // Configure thread pool if specified
+    if args.threads > 0 {
+        ThreadPoolBuilder::new()
+            .num_threads(args.threads)
+            .build_global()
+            .expect("Failed to build thread pool");
+    }
+
+    let (algorithm, header, claims, signature) = split_jwt(&args.jwt).expect("bad jwt");
+
+    if matches!(
+        algorithm,
+        AlgorithmType::Rs256 | AlgorithmType::Rs384 | AlgorithmType::Rs512
+    ) {
+        match &args.public_key {
+            Some(public_key) => run_key_confusion(public_key, algorithm, header, claims, signature),
+            None => {
+                eprintln!("Currently only deal with HS{{256, 384, 512}} algorithms -- if you want to implement other ones, please submit a PR");
+                eprintln!("(for RS{{256, 384, 512}} tokens, pass --public-key <PEM> to try the classic algorithm-confusion attack instead)");
+                exit(1);
+            }
+        }
+    }
+
+    let wordlist_path = args
+        .wordlist
+        .as_ref()
+        .expect("wordlist is required unless cracking an RS256/384/512 token with --public-key");
+    let file = File::open(wordlist_path).expect("wordlist should exist");
+    // Safety: the wordlist is only read, and we don't care about it being
+    // mutated out from under us mid-scan -- worst case we read stale bytes.
+    let mmap = unsafe { Mmap::map(&file).expect("wordlist should be mappable") };
+    let total_bytes = mmap.len();
+
+    // Calculate chunk size for progress reporting (minimum 1). There's no
+    // upfront line count anymore, so progress is tracked by bytes scanned
+    // instead of words scanned.
+    let chunk = std::cmp::max(1, total_bytes / 100);
+
+    let processed_bytes = Arc::new(AtomicUsize::new(0));
+
+    // Create the key generator function based on algorithm type
+    let create_key: fn(&[u8]) -> Box<dyn VerifyingAlgorithm> = match algorithm {
+        AlgorithmType::Hs256 => |word| Box::new(Hmac::<Sha256>::new_from_slice(word).expect("this shouldn't fail")),
+        AlgorithmType::Hs384 => |word| Box::new(Hmac::<Sha384>::new_from_slice(word).expect("this shouldn't fail")),
+        AlgorithmType::Hs512 => |word| Box::new(Hmac::<Sha512>::new_from_slice(word).expect("this shouldn't fail")),
+        AlgorithmType::None => {
+            println!("None type specified - nothing to crack");
+            exit(0);
+        }
+        _ => {
+            eprintln!("Currently only deal with HS{{256, 384, 512}} algorithms -- if you want to implement other ones, please submit a PR");
+            exit(1);
+        }
+    };
+
+    // PBKDF2 salt decoded once up front; present only if --pbkdf2-salt was given.
+    let pbkdf2_salt = args
+        .pbkdf2_salt
+        .as_deref()
+        .map(|salt| hex::decode(salt).expect("--pbkdf2-salt should be valid hex"));
+
+    // Rules file parsed once up front; present only if --rules was given.
+    let rules = args.rules.as_deref().map(rules::parse_rules_file);
+
+    // Split the mapped file into one byte range per worker. Each range is
+    // aligned to the next line boundary so workers never share an iterator
+    // or re-decode lines another worker already owns.
+    let num_workers = rayon::current_num_threads().max(1);
+    let nominal_chunk = std::cmp::max(1, total_bytes / num_workers);
+    let ranges: Vec<(usize, usize)> = (0..num_workers)
+        .map(|i| {
+            let start = align_to_next_line(&mmap, i * nominal_chunk);
+            let end = if i == num_workers - 1 {
+                total_bytes
+            } else {
+                align_to_next_line(&mmap, (i + 1) * nominal_chunk)
+            };
+            (start, end)
+        })
+        .collect();
+
+    ranges.into_par_iter().for_each(|(start, end)| {
+        if start >= end {
+            return;
+        }
+
+        for line in mmap[start..end].split(|&b| b == b'\n') {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line.is_empty() {
+                continue;
+            }
+            let Ok(word) = std::str::from_utf8(line) else {
+                continue;
+            };
+
+            // Update progress counter (by bytes scanned, including the newline)
+            let p = processed_bytes.fetch_add(line.len() + 1, Ordering::Relaxed);
+
+            let try_candidate = |candidate: &str| {
+                // If a PBKDF2 salt was given, derive the key material from
+                // the candidate word instead of using it directly.
+                let derived_key;
+                let key_bytes: &[u8] = match &pbkdf2_salt {
+                    Some(salt) => {
+                        let mut dk = vec![0u8; args.pbkdf2_dklen];
+                        pbkdf2::pbkdf2_hmac::<Sha256>(
+                            candidate.as_bytes(),
+                            salt,
+                            args.pbkdf2_iterations,
+                            &mut dk,
+                        );
+                        derived_key = dk;
+                        &derived_key
+                    }
+                    None => candidate.as_bytes(),
+                };
+
+                let key = create_key(key_bytes);
+
+                if p.is_multiple_of(chunk) {
+                    print!("\r[alg: {:#?}] [total bytes: {total_bytes}]: {}%", key.algorithm_type(), (p * 100) / total_bytes);
+                    std::io::stdout().flush().unwrap();
+                }
+
+                if key.verify(header, claims, signature).unwrap_or(false) {
+                    println!("\nFound secret: {candidate:?}");
+                    report_claims(claims);
+                    exit(0)
+                }
+            };
+
+            match &rules {
+                Some(rules) => {
+                    for rule in rules {
+                        rules::expand(word, rule, &mut |variant| try_candidate(&variant));
+                    }
+                }
+                None => try_candidate(word),
+            }
+        }
+    });
+
+    println!("\nNo secret found, try another wordlist.");
+}
+
+/// Find the start of the first line that begins after `pos` in `mmap`.
+/// Position `0` is always already line-aligned. Any other position is
+/// advanced past the next `\n`, handing ownership of whatever line `pos`
+/// landed in to the worker scanning the previous range.
+fn align_to_next_line(mmap: &Mmap, pos: usize) -> usize {
+    if pos == 0 || pos >= mmap.len() {
+        return pos.min(mmap.len());
+    }
+
+    match mmap[pos..].iter().position(|&b| b == b'\n') {
+        Some(offset) => pos + offset + 1,
+        None => mmap.len(),
+    }
+}
+
+/// Algorithm-confusion mode: instead of searching a wordlist, treat the
+/// RSA/EC public key itself as the HMAC secret. Vulnerable servers that
+/// accept both RS256 and HS256 (and use the same key material for both)
+/// can be tricked into verifying a forged HS256 token this way. Real-world
+/// servers differ on exactly which byte representation of the key they
+/// feed to the HMAC, so we try a handful of them.
+fn run_key_confusion(
+    public_key: &PathBuf,
+    algorithm: AlgorithmType,
+    header: &str,
+    claims: &str,
+    signature: &str,
+) -> ! {
+    let pem_string =
+        std::fs::read_to_string(public_key).expect("public key file should be readable");
+
+    let mut candidates: Vec<(&str, Vec<u8>)> = vec![
+        ("raw PEM bytes (as read)", pem_string.clone().into_bytes()),
+        (
+            "PEM without trailing newline",
+            pem_string.trim_end().as_bytes().to_vec(),
+        ),
+        (
+            "PEM with trailing newline",
+            format!("{}\n", pem_string.trim_end()).into_bytes(),
+        ),
+    ];
+
+    if let Some(der) = pem_to_der(&pem_string) {
+        candidates.push(("DER bytes (base64-decoded)", der));
+    }
+
+    for (label, key_bytes) in candidates {
+        // `Mac::verify` (by-value, takes a tag) and `VerifyingAlgorithm::verify`
+        // (by-ref, takes header/claims/signature) share a name, so this must be
+        // called via fully-qualified syntax to resolve to the latter.
+        let matched = match algorithm {
+            AlgorithmType::Rs256 => VerifyingAlgorithm::verify(
+                &Hmac::<Sha256>::new_from_slice(&key_bytes).expect("this shouldn't fail"),
+                header,
+                claims,
+                signature,
+            )
+            .unwrap_or(false),
+            AlgorithmType::Rs384 => VerifyingAlgorithm::verify(
+                &Hmac::<Sha384>::new_from_slice(&key_bytes).expect("this shouldn't fail"),
+                header,
+                claims,
+                signature,
+            )
+            .unwrap_or(false),
+            AlgorithmType::Rs512 => VerifyingAlgorithm::verify(
+                &Hmac::<Sha512>::new_from_slice(&key_bytes).expect("this shouldn't fail"),
+                header,
+                claims,
+                signature,
+            )
+            .unwrap_or(false),
+            _ => unreachable!("run_key_confusion is only called for RS{{256,384,512}}"),
+        };
+
+        if matched {
+            println!("Found algorithm-confusion match using: {label}");
+            println!("Forge tokens with this representation as the HS{{256,384,512}} secret.");
+            report_claims(claims);
+            exit(0);
+        }
+    }
+
+    println!("\nNo representation of the public key matched as an HMAC secret.");
+    exit(1);
+}
+
+/// Strip the PEM armor (`-----BEGIN ... -----` / `-----END ... -----`) and
+/// base64-decode the remaining body into raw DER bytes.
+fn pem_to_der(pem: &str) -> Option<Vec<u8>> {
+    let body: String = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+
+    base64::engine::general_purpose::STANDARD
+        .decode(body)
+        .ok()
+}
+
+/// Decode the base64url claims segment of a cracked token, pretty-print it,
+/// and report whether the registered time-based claims (`exp`, `nbf`, `iat`)
+/// currently hold against wall-clock time.
+fn report_claims(claims: &str) {
+    let Ok(decoded) = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(claims) else {
+        eprintln!("(claims segment is not valid base64url, skipping decode)");
+        return;
+    };
+
+    let Ok(value) = serde_json::from_slice::<serde_json::Value>(&decoded) else {
+        eprintln!("(claims segment is not valid JSON, skipping decode)");
+        return;
+    };
+
+    println!(
+        "\nDecoded claims:\n{}",
+        serde_json::to_string_pretty(&value).unwrap_or_default()
+    );
+
+    let now = chrono::Utc::now().timestamp();
+
+    if let Some(exp) = value.get("exp").and_then(|v| v.as_i64()) {
+        if exp < now {
+            println!("exp: expired ({exp} < current time {now})");
+        } else {
+            println!("exp: still valid ({exp} >= current time {now})");
+        }
+    }
+
+    if let Some(nbf) = value.get("nbf").and_then(|v| v.as_i64()) {
+        if now < nbf {
+            println!("nbf: not yet valid ({now} < {nbf})");
+        } else {
+            println!("nbf: already valid ({now} >= {nbf})");
+        }
+    }
+
+    if let Some(iat) = value.get("iat").and_then(|v| v.as_i64()) {
+        println!("iat: issued at {iat} ({} seconds ago)", now - iat);
+    }
+}
+
+fn split_jwt(jwt: &str) -> Result<(AlgorithmType, &str, &str, &str), jwt::Error> {
+    let mut components = jwt.split('.');
+    let header = components.next().ok_or(jwt::Error::NoHeaderComponent)?;
+    let claims = components.next().ok_or(jwt::Error::NoClaimsComponent)?;
+    let signature = components.next().ok_or(jwt::Error::NoSignatureComponent)?;
+
+    let algorithm = jwt::Header::from_base64(header)?.algorithm;
+
+    Ok((algorithm, header, claims, signature))
+}
@@ -0,0 +1,136 @@
+use std::path::Path;
+
+/// A single hashcat-style word-mangling operation.
+#[derive(Debug, Clone, Copy)]
+enum RuleOp {
+    /// `c` - capitalize the first character
+    Capitalize,
+    /// `u` - uppercase the whole word
+    Upper,
+    /// `l` - lowercase the whole word
+    Lower,
+    /// `r` - reverse the word
+    Reverse,
+    /// `d` - duplicate the word
+    Duplicate,
+    /// `$X` - append a literal character
+    Append(char),
+    /// `^X` - prepend a literal character
+    Prepend(char),
+    /// `$X..$Y` - fan out into one variant per appended character in the range
+    AppendRange(char, char),
+}
+
+/// A rule is a sequence of operations applied in order to a candidate word.
+#[derive(Debug, Clone)]
+pub struct Rule(Vec<RuleOp>);
+
+/// Parse a hashcat-style rules file: one rule per non-empty, non-comment
+/// (`#`) line.
+pub fn parse_rules_file(path: &Path) -> Vec<Rule> {
+    std::fs::read_to_string(path)
+        .expect("rules file should be readable")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_rule_line)
+        .collect()
+}
+
+fn parse_rule_line(line: &str) -> Rule {
+    let chars: Vec<char> = line.chars().collect();
+    let mut ops = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            'c' => {
+                ops.push(RuleOp::Capitalize);
+                i += 1;
+            }
+            'u' => {
+                ops.push(RuleOp::Upper);
+                i += 1;
+            }
+            'l' => {
+                ops.push(RuleOp::Lower);
+                i += 1;
+            }
+            'r' => {
+                ops.push(RuleOp::Reverse);
+                i += 1;
+            }
+            'd' => {
+                ops.push(RuleOp::Duplicate);
+                i += 1;
+            }
+            '$' if i + 5 < chars.len() && chars[i + 2] == '.' && chars[i + 3] == '.' && chars[i + 4] == '$' => {
+                ops.push(RuleOp::AppendRange(chars[i + 1], chars[i + 5]));
+                i += 6;
+            }
+            '$' if i + 1 < chars.len() => {
+                ops.push(RuleOp::Append(chars[i + 1]));
+                i += 2;
+            }
+            '^' if i + 1 < chars.len() => {
+                ops.push(RuleOp::Prepend(chars[i + 1]));
+                i += 2;
+            }
+            _ => i += 1, // whitespace between ops, or an unsupported operator
+        }
+    }
+
+    Rule(ops)
+}
+
+/// Apply `rule` to `word`, streaming each resulting variant to `visit` as
+/// it's produced rather than materializing the full expansion up front.
+/// Most rules produce exactly one variant; `$X..$Y` range ops fan out into
+/// one variant per character in the range.
+pub fn expand(word: &str, rule: &Rule, visit: &mut impl FnMut(String)) {
+    expand_from(&rule.0, 0, word.to_string(), visit);
+}
+
+fn expand_from(ops: &[RuleOp], idx: usize, current: String, visit: &mut impl FnMut(String)) {
+    let Some(op) = ops.get(idx) else {
+        visit(current);
+        return;
+    };
+
+    match *op {
+        RuleOp::Capitalize => {
+            let mut chars = current.chars();
+            let capitalized = match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => current,
+            };
+            expand_from(ops, idx + 1, capitalized, visit);
+        }
+        RuleOp::Upper => expand_from(ops, idx + 1, current.to_uppercase(), visit),
+        RuleOp::Lower => expand_from(ops, idx + 1, current.to_lowercase(), visit),
+        RuleOp::Reverse => expand_from(ops, idx + 1, current.chars().rev().collect(), visit),
+        RuleOp::Duplicate => {
+            let mut doubled = current.clone();
+            doubled.push_str(&current);
+            expand_from(ops, idx + 1, doubled, visit);
+        }
+        RuleOp::Append(c) => {
+            let mut appended = current;
+            appended.push(c);
+            expand_from(ops, idx + 1, appended, visit);
+        }
+        RuleOp::Prepend(c) => {
+            let mut prepended = String::with_capacity(current.len() + c.len_utf8());
+            prepended.push(c);
+            prepended.push_str(&current);
+            expand_from(ops, idx + 1, prepended, visit);
+        }
+        RuleOp::AppendRange(from, to) => {
+            for c in from..=to {
+                let mut variant = current.clone();
+                variant.push(c);
+                expand_from(ops, idx + 1, variant, visit);
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+
+use base64::Engine;
+use clap::{Args as ClapArgs, ValueEnum};
+use hmac::{Hmac, Mac};
+use sha2::{Sha256, Sha384, Sha512};
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Algorithm {
+    Hs256,
+    Hs384,
+    Hs512,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct Args {
+    /// HMAC secret to sign the token with
+    #[arg(long)]
+    secret: String,
+
+    /// Algorithm to sign with
+    #[arg(long, value_enum, default_value_t = Algorithm::Hs256)]
+    alg: Algorithm,
+
+    /// Claims to embed in the token: either inline JSON (`{"admin":true}`)
+    /// or a path to a file containing JSON
+    claims: String,
+}
+
+pub fn run(args: Args) {
+    let claims_json = match std::fs::read_to_string(PathBuf::from(&args.claims)) {
+        Ok(contents) => contents,
+        Err(_) => args.claims.clone(),
+    };
+
+    let claims: serde_json::Value =
+        serde_json::from_str(&claims_json).expect("claims should be valid JSON");
+
+    let alg_name = match args.alg {
+        Algorithm::Hs256 => "HS256",
+        Algorithm::Hs384 => "HS384",
+        Algorithm::Hs512 => "HS512",
+    };
+
+    let header = serde_json::json!({ "alg": alg_name, "typ": "JWT" });
+
+    let b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    let header_b64 = b64.encode(serde_json::to_vec(&header).expect("header should serialize"));
+    let claims_b64 = b64.encode(serde_json::to_vec(&claims).expect("claims should serialize"));
+
+    let signing_input = format!("{header_b64}.{claims_b64}");
+    let secret = args.secret.as_bytes();
+
+    let signature = match args.alg {
+        Algorithm::Hs256 => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("this shouldn't fail");
+            mac.update(signing_input.as_bytes());
+            mac.finalize().into_bytes().to_vec()
+        }
+        Algorithm::Hs384 => {
+            let mut mac = Hmac::<Sha384>::new_from_slice(secret).expect("this shouldn't fail");
+            mac.update(signing_input.as_bytes());
+            mac.finalize().into_bytes().to_vec()
+        }
+        Algorithm::Hs512 => {
+            let mut mac = Hmac::<Sha512>::new_from_slice(secret).expect("this shouldn't fail");
+            mac.update(signing_input.as_bytes());
+            mac.finalize().into_bytes().to_vec()
+        }
+    };
+
+    let signature_b64 = b64.encode(signature);
+
+    println!("{signing_input}.{signature_b64}");
+}